@@ -0,0 +1,45 @@
+use actix_web::{post, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::auth::jwt::issue_bearer_token;
+use crate::db::util::DbPool;
+use crate::model::user::User;
+use crate::server_error::ServerError;
+
+#[derive(Deserialize)]
+pub struct LoginBody {
+    uid: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+pub struct TokenResponse {
+    token: String,
+}
+
+#[post("/login")]
+pub async fn login(
+    body: web::Json<LoginBody>,
+    pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ServerError> {
+    let user = User::find_by_uid(pool.get_ref(), &body.uid)
+        .await
+        .map_err(|_| ServerError::Unauthorized {
+            reason: "invalid uid or password".into(),
+        })?;
+    if !user.verify_password(&body.password) {
+        return Err(ServerError::Unauthorized {
+            reason: "invalid uid or password".into(),
+        });
+    }
+    let token = issue_bearer_token(&body.uid)?;
+    Ok(HttpResponse::Ok().json(TokenResponse { token }))
+}
+
+#[post("/guest-login")]
+pub async fn guest_login() -> Result<HttpResponse, ServerError> {
+    let uid = Uuid::new_v4().simple().to_string();
+    let token = issue_bearer_token(&uid)?;
+    Ok(HttpResponse::Ok().json(TokenResponse { token }))
+}
@@ -0,0 +1,127 @@
+use actix_web::{get, post, web, HttpResponse};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use rand::random;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::db::schema::game_challenges::dsl::game_challenges as game_challenges_table;
+use crate::db::schema::games_users::dsl::games_users as games_users_table;
+use crate::db::util::{get_conn, DbPool};
+use crate::extractors::auth::AuthenticatedUser;
+use crate::model::game::{Game, NewGame};
+use crate::model::game_challenge::{GameChallenge, NewGameChallenge};
+use crate::model::games_user::GamesUser;
+use crate::server_error::ServerError;
+
+const VALID_COLOR_CHOICES: &[&str] = &["white", "black", "random"];
+
+#[derive(Deserialize)]
+pub struct NewGameChallengeBody {
+    game_type: String,
+    ranked: bool,
+    public: bool,
+    tournament_queen_rule: bool,
+    color_choice: String,
+}
+
+#[post("/game-challenge")]
+pub async fn create_game_challenge(
+    body: web::Json<NewGameChallengeBody>,
+    auth_user: AuthenticatedUser,
+    pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ServerError> {
+    if !VALID_COLOR_CHOICES.contains(&body.color_choice.as_str()) {
+        return Err(ServerError::UserInputError {
+            field: "color_choice".into(),
+            reason: format!("must be one of {:?}", VALID_COLOR_CHOICES),
+        });
+    }
+
+    let new_challenge = NewGameChallenge {
+        id: Uuid::new_v4(),
+        challenger_uid: auth_user.uid,
+        game_type: body.game_type.clone(),
+        ranked: body.ranked,
+        public: body.public,
+        tournament_queen_rule: body.tournament_queen_rule,
+        color_choice: body.color_choice.clone(),
+    };
+    let challenge = GameChallenge::insert(&new_challenge, pool.get_ref()).await?;
+    Ok(HttpResponse::Created().json(challenge))
+}
+
+#[get("/game-challenges")]
+pub async fn list_game_challenges(pool: web::Data<DbPool>) -> Result<HttpResponse, ServerError> {
+    let challenges = GameChallenge::list_public(pool.get_ref()).await?;
+    Ok(HttpResponse::Ok().json(challenges))
+}
+
+#[post("/game-challenge/{id}/accept")]
+pub async fn accept_game_challenge(
+    id: web::Path<Uuid>,
+    auth_user: AuthenticatedUser,
+    pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ServerError> {
+    let challenge_id = id.into_inner();
+    let acceptor_uid = auth_user.uid;
+    let conn = &mut get_conn(pool.get_ref()).await?;
+
+    let game: Game = conn
+        .transaction(|conn| {
+            async move {
+                let challenge: GameChallenge = diesel::QueryDsl::find(game_challenges_table, challenge_id)
+                    .first(conn)
+                    .await?;
+
+                if challenge.challenger_uid == acceptor_uid {
+                    return Err(ServerError::Forbidden {
+                        reason: "cannot accept your own game challenge".into(),
+                    });
+                }
+
+                diesel::delete(diesel::QueryDsl::find(game_challenges_table, challenge_id))
+                    .execute(conn)
+                    .await?;
+
+                let (white_uid, black_uid) = match challenge.color_choice.as_str() {
+                    "white" => (challenge.challenger_uid.clone(), acceptor_uid.clone()),
+                    "black" => (acceptor_uid.clone(), challenge.challenger_uid.clone()),
+                    _ if random::<bool>() => (challenge.challenger_uid.clone(), acceptor_uid.clone()),
+                    _ => (acceptor_uid.clone(), challenge.challenger_uid.clone()),
+                };
+
+                let new_game = NewGame::new(
+                    &white_uid,
+                    &black_uid,
+                    &challenge.game_type,
+                    challenge.tournament_queen_rule,
+                );
+                let game: Game = diesel::insert_into(crate::db::schema::games::dsl::games)
+                    .values(&new_game)
+                    .get_result(conn)
+                    .await?;
+
+                let players = vec![
+                    GamesUser {
+                        game_id: game.id,
+                        user_id: white_uid,
+                    },
+                    GamesUser {
+                        game_id: game.id,
+                        user_id: black_uid,
+                    },
+                ];
+                diesel::insert_into(games_users_table)
+                    .values(&players)
+                    .execute(conn)
+                    .await?;
+
+                Ok(game)
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(HttpResponse::Created().json(game))
+}
@@ -4,8 +4,10 @@ use serde::Deserialize;
 
 use crate::db::util::DbPool;
 use crate::extractors::auth::AuthenticatedUser;
-use crate::model::user::User;
+use crate::extractors::session::SessionUser;
+use crate::model::user::{validate_username_rules, User};
 use crate::server_error::ServerError;
+use crate::session::session_cookie;
 
 #[get("/user/{uid}")]
 pub async fn get_user(
@@ -16,9 +18,36 @@ pub async fn get_user(
     Ok(HttpResponse::Ok().json(user))
 }
 
+const MIN_PASSWORD_LENGTH: usize = 8;
+
 #[derive(Deserialize)]
 pub struct NewUserBody {
     username: String,
+    password: String,
+}
+
+impl NewUserBody {
+    /// Runs every username rule plus a uniqueness check, collecting all
+    /// failures instead of stopping at the first.
+    pub async fn validate(&self, pool: &DbPool) -> Result<(), ServerError> {
+        let mut errors = validate_username_rules(&self.username);
+        if User::username_taken(pool, &self.username).await? {
+            errors.push(("username".into(), "username is already taken".into()));
+        }
+        if self.password.len() < MIN_PASSWORD_LENGTH {
+            errors.push((
+                "password".into(),
+                format!("password must be >= {} chars", MIN_PASSWORD_LENGTH),
+            ));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ServerError::UserInputErrors(
+                errors.into_iter().map(Into::into).collect(),
+            ))
+        }
+    }
 }
 
 fn random_guest_name() -> String {
@@ -28,11 +57,12 @@ fn random_guest_name() -> String {
 
 #[post("/user")]
 pub async fn create_user(
-    user: web::Json<NewUserBody>,
+    body: web::Json<NewUserBody>,
     auth_user: AuthenticatedUser,
     pool: web::Data<DbPool>,
 ) -> Result<HttpResponse, ServerError> {
-    let user = User::new(&auth_user.uid, &user.username, false)?;
+    body.validate(pool.get_ref()).await?;
+    let user = User::new(&auth_user.uid, &body.username, Some(&body.password), false)?;
     user.insert(&pool).await?;
     Ok(HttpResponse::Created().json(user))
 }
@@ -42,7 +72,22 @@ pub async fn create_guest_user(
     auth_user: AuthenticatedUser,
     pool: web::Data<DbPool>,
 ) -> Result<HttpResponse, ServerError> {
-    let user = User::new(&auth_user.uid, &random_guest_name(), true)?;
+    let user = User::new(&auth_user.uid, &random_guest_name(), None, true)?;
     user.insert(&pool).await?;
-    Ok(HttpResponse::Created().json(user))
+    let cookie = session_cookie(user.uid())?;
+    Ok(HttpResponse::Created().cookie(cookie).json(user))
+}
+
+#[post("/user/upgrade")]
+pub async fn upgrade_user(
+    body: web::Json<NewUserBody>,
+    session_user: SessionUser,
+    pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ServerError> {
+    body.validate(pool.get_ref()).await?;
+    let mut user = User::find_by_uid(pool.get_ref(), &session_user.uid).await?;
+    user.promote_from_guest(pool.get_ref(), &body.username, &body.password)
+        .await?;
+    let cookie = session_cookie(user.uid())?;
+    Ok(HttpResponse::Ok().cookie(cookie).json(user))
 }
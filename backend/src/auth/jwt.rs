@@ -0,0 +1,82 @@
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::server_error::ServerError;
+
+const TOKEN_TTL_SECONDS: u64 = 60 * 60 * 24;
+
+/// Scopes a token to the channel it was issued for, so a token minted for
+/// one can't be replayed on the other (e.g. an `Authorization: Bearer`
+/// token presented as a `session` cookie).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    Bearer,
+    Session,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub iat: u64,
+    pub exp: u64,
+    pub typ: TokenType,
+}
+
+fn jwt_secret() -> String {
+    env::var("JWT_SECRET").expect("JWT_SECRET must be set")
+}
+
+fn issue_token(uid: &str, typ: TokenType) -> Result<String, ServerError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the unix epoch")
+        .as_secs();
+
+    let claims = Claims {
+        sub: uid.to_string(),
+        iat: now,
+        exp: now + TOKEN_TTL_SECONDS,
+        typ,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(jwt_secret().as_bytes()),
+    )
+    .map_err(|_| ServerError::Unauthorized {
+        reason: "failed to issue token".into(),
+    })
+}
+
+pub fn issue_bearer_token(uid: &str) -> Result<String, ServerError> {
+    issue_token(uid, TokenType::Bearer)
+}
+
+pub fn issue_session_token(uid: &str) -> Result<String, ServerError> {
+    issue_token(uid, TokenType::Session)
+}
+
+pub fn verify_token(token: &str, expected_type: TokenType) -> Result<Claims, ServerError> {
+    let claims = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(jwt_secret().as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|_| ServerError::Unauthorized {
+        reason: "invalid or expired token".into(),
+    })?;
+
+    if claims.typ != expected_type {
+        return Err(ServerError::Unauthorized {
+            reason: "token is not valid for this channel".into(),
+        });
+    }
+
+    Ok(claims)
+}
@@ -38,6 +38,7 @@ diesel::table! {
         uid -> Text,
         username -> Varchar,
         is_guest -> Bool,
+        password_hash -> Nullable<Text>,
     }
 }
 
@@ -0,0 +1,38 @@
+use std::future::{ready, Ready};
+
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+
+use crate::auth::jwt::{verify_token, TokenType};
+use crate::server_error::ServerError;
+
+pub struct AuthenticatedUser {
+    pub uid: String,
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = ServerError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(authenticate(req))
+    }
+}
+
+fn authenticate(req: &HttpRequest) -> Result<AuthenticatedUser, ServerError> {
+    let header = req
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ServerError::Unauthorized {
+            reason: "missing Authorization header".into(),
+        })?;
+
+    let token = header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| ServerError::Unauthorized {
+            reason: "Authorization header must use the Bearer scheme".into(),
+        })?;
+
+    let claims = verify_token(token, TokenType::Bearer)?;
+    Ok(AuthenticatedUser { uid: claims.sub })
+}
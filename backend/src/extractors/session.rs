@@ -0,0 +1,30 @@
+use std::future::{ready, Ready};
+
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+
+use crate::auth::jwt::{verify_token, TokenType};
+use crate::server_error::ServerError;
+use crate::session::SESSION_COOKIE_NAME;
+
+pub struct SessionUser {
+    pub uid: String,
+}
+
+impl FromRequest for SessionUser {
+    type Error = ServerError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(authenticate(req))
+    }
+}
+
+fn authenticate(req: &HttpRequest) -> Result<SessionUser, ServerError> {
+    let cookie = req
+        .cookie(SESSION_COOKIE_NAME)
+        .ok_or_else(|| ServerError::Unauthorized {
+            reason: "missing session cookie".into(),
+        })?;
+    let claims = verify_token(cookie.value(), TokenType::Session)?;
+    Ok(SessionUser { uid: claims.sub })
+}
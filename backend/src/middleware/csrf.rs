@@ -0,0 +1,229 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+use actix_web::body::EitherBody;
+use actix_web::cookie::Cookie;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::Method;
+use actix_web::{Error, HttpMessage};
+use futures_util::future::LocalBoxFuture;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use crate::server_error::ServerError;
+
+#[derive(Clone)]
+pub struct CsrfConfig {
+    pub cookie_name: String,
+    pub header_name: String,
+    pub token_length: usize,
+}
+
+impl Default for CsrfConfig {
+    fn default() -> Self {
+        CsrfConfig {
+            cookie_name: "csrf_token".into(),
+            header_name: "X-CSRF-Token".into(),
+            token_length: 32,
+        }
+    }
+}
+
+pub struct Csrf {
+    config: Rc<CsrfConfig>,
+}
+
+impl Csrf {
+    pub fn new(config: CsrfConfig) -> Self {
+        Csrf {
+            config: Rc::new(config),
+        }
+    }
+}
+
+impl Default for Csrf {
+    fn default() -> Self {
+        Csrf::new(CsrfConfig::default())
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Csrf
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = CsrfMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CsrfMiddleware {
+            service: Rc::new(service),
+            config: self.config.clone(),
+        }))
+    }
+}
+
+pub struct CsrfMiddleware<S> {
+    service: Rc<S>,
+    config: Rc<CsrfConfig>,
+}
+
+fn is_bearer_authenticated(req: &ServiceRequest) -> bool {
+    req.headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("Bearer "))
+}
+
+fn generate_token(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+// Not vulnerable to timing attacks: always compares every byte regardless of
+// where the inputs first differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl<S, B> Service<ServiceRequest> for CsrfMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let config = Rc::clone(&self.config);
+
+        Box::pin(async move {
+            if is_bearer_authenticated(&req) {
+                return service.call(req).await.map(ServiceResponse::map_into_left_body);
+            }
+
+            if req.method() == Method::GET {
+                let needs_cookie = req.cookie(&config.cookie_name).is_none();
+                let mut res = service.call(req).await?.map_into_left_body();
+                if needs_cookie {
+                    let token = generate_token(config.token_length);
+                    let cookie = Cookie::build(config.cookie_name.clone(), token)
+                        .http_only(false)
+                        .secure(true)
+                        .path("/")
+                        .finish();
+                    let _ = res.response_mut().add_cookie(&cookie);
+                }
+                return Ok(res);
+            }
+
+            let cookie_token = req.cookie(&config.cookie_name).map(|c| c.value().to_string());
+            let header_token = req
+                .headers()
+                .get(&config.header_name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+
+            let tokens_match = matches!(
+                (cookie_token, header_token),
+                (Some(cookie_value), Some(header_value))
+                    if constant_time_eq(cookie_value.as_bytes(), header_value.as_bytes())
+            );
+
+            if tokens_match {
+                service.call(req).await.map(ServiceResponse::map_into_left_body)
+            } else {
+                Ok(req.error_response(ServerError::CsrfError).map_into_right_body())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::{test, web, App, HttpResponse};
+
+    async fn ok() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_web::test]
+    async fn tests_get_request_issues_csrf_cookie_when_missing() {
+        let app = test::init_service(App::new().wrap(Csrf::default()).route("/", web::get().to(ok))).await;
+
+        let req = test::TestRequest::get().uri("/").to_request();
+        let res = test::call_service(&app, req).await;
+
+        let config = CsrfConfig::default();
+        assert!(res.response().cookies().any(|c| c.name() == config.cookie_name));
+    }
+
+    #[actix_web::test]
+    async fn tests_post_with_matching_cookie_and_header_is_allowed() {
+        let app = test::init_service(App::new().wrap(Csrf::default()).route("/", web::post().to(ok))).await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .cookie(Cookie::new("csrf_token", "matching-token"))
+            .insert_header(("X-CSRF-Token", "matching-token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn tests_post_with_mismatched_token_is_rejected() {
+        let app = test::init_service(App::new().wrap(Csrf::default()).route("/", web::post().to(ok))).await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .cookie(Cookie::new("csrf_token", "one-token"))
+            .insert_header(("X-CSRF-Token", "other-token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn tests_post_with_missing_cookie_is_rejected() {
+        let app = test::init_service(App::new().wrap(Csrf::default()).route("/", web::post().to(ok))).await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .insert_header(("X-CSRF-Token", "some-token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn tests_bearer_authenticated_request_bypasses_csrf_check() {
+        let app = test::init_service(App::new().wrap(Csrf::default()).route("/", web::post().to(ok))).await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .insert_header(("Authorization", "Bearer some-token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
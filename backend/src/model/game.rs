@@ -0,0 +1,55 @@
+use diesel::{result::Error, Identifiable, Insertable, QueryDsl, Queryable};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+
+use crate::db::schema::games;
+use crate::db::schema::games::dsl::games as games_table;
+use crate::db::util::{get_conn, DbPool};
+
+const INITIAL_GAME_STATUS: &str = "NotStarted";
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(primary_key(id))]
+pub struct Game {
+    pub id: i32,
+    pub black_uid: String,
+    pub game_status: String,
+    pub game_type: String,
+    pub history: String,
+    pub tournament_queen_rule: bool,
+    pub turn: i32,
+    pub white_uid: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = games)]
+pub struct NewGame {
+    pub black_uid: String,
+    pub game_status: String,
+    pub game_type: String,
+    pub history: String,
+    pub tournament_queen_rule: bool,
+    pub turn: i32,
+    pub white_uid: String,
+}
+
+impl NewGame {
+    pub fn new(white_uid: &str, black_uid: &str, game_type: &str, tournament_queen_rule: bool) -> NewGame {
+        NewGame {
+            black_uid: black_uid.into(),
+            game_status: INITIAL_GAME_STATUS.into(),
+            game_type: game_type.into(),
+            history: String::new(),
+            tournament_queen_rule,
+            turn: 0,
+            white_uid: white_uid.into(),
+        }
+    }
+}
+
+impl Game {
+    pub async fn find_by_id(pool: &DbPool, id: i32) -> Result<Game, Error> {
+        let conn = &mut get_conn(pool).await?;
+        games_table.find(id).first(conn).await
+    }
+}
@@ -0,0 +1,58 @@
+use diesel::{result::Error, ExpressionMethods, Identifiable, Insertable, QueryDsl, Queryable};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::schema::game_challenges;
+use crate::db::schema::game_challenges::dsl::game_challenges as game_challenges_table;
+use crate::db::util::{get_conn, DbPool};
+
+#[derive(Queryable, Identifiable, Insertable, Serialize, Deserialize, Debug)]
+#[diesel(primary_key(id))]
+pub struct GameChallenge {
+    pub id: Uuid,
+    pub challenger_uid: String,
+    pub game_type: String,
+    pub ranked: bool,
+    pub public: bool,
+    pub tournament_queen_rule: bool,
+    pub color_choice: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = game_challenges)]
+pub struct NewGameChallenge {
+    pub id: Uuid,
+    pub challenger_uid: String,
+    pub game_type: String,
+    pub ranked: bool,
+    pub public: bool,
+    pub tournament_queen_rule: bool,
+    pub color_choice: String,
+}
+
+impl GameChallenge {
+    pub async fn insert(new_challenge: &NewGameChallenge, pool: &DbPool) -> Result<GameChallenge, Error> {
+        let conn = &mut get_conn(pool).await?;
+        diesel::insert_into(game_challenges_table)
+            .values(new_challenge)
+            .get_result(conn)
+            .await
+    }
+
+    pub async fn find_by_id(pool: &DbPool, id: Uuid) -> Result<GameChallenge, Error> {
+        let conn = &mut get_conn(pool).await?;
+        game_challenges_table.find(id).first(conn).await
+    }
+
+    pub async fn list_public(pool: &DbPool) -> Result<Vec<GameChallenge>, Error> {
+        use crate::db::schema::game_challenges::dsl::public;
+
+        let conn = &mut get_conn(pool).await?;
+        game_challenges_table
+            .filter(public.eq(true))
+            .load(conn)
+            .await
+    }
+}
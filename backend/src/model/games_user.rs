@@ -0,0 +1,11 @@
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::db::schema::games_users;
+
+#[derive(Insertable, Queryable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = games_users)]
+pub struct GamesUser {
+    pub game_id: i32,
+    pub user_id: String,
+}
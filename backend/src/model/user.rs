@@ -2,12 +2,17 @@ use crate::db::schema::users;
 use crate::db::schema::users::dsl::users as users_table;
 use crate::db::util::{get_conn, DbPool};
 use crate::server_error::ServerError;
-use diesel::{result::Error, Identifiable, Insertable, QueryDsl, Queryable};
+use diesel::{
+    result::Error, ExpressionMethods, Identifiable, Insertable, OptionalExtension,
+    PgTextExpressionMethods, QueryDsl, Queryable,
+};
 use diesel_async::RunQueryDsl;
 use serde::{Deserialize, Serialize};
 
+const MIN_USERNAME_LENGTH: usize = 3;
 const MAX_USERNAME_LENGTH: usize = 40;
 const VALID_USERNAME_CHARS: &str = "-_";
+const RESERVED_USERNAMES: &[&str] = &["admin", "guest", "system", "root", "moderator"];
 
 fn valid_uid_char(c: char) -> bool {
     c.is_ascii_alphanumeric()
@@ -27,21 +32,70 @@ fn valid_username_char(c: char) -> bool {
     c.is_ascii_alphanumeric() || VALID_USERNAME_CHARS.contains(c)
 }
 
+/// Runs every username rule via [`validate_username_rules`] and returns the
+/// first failure. Used by call sites that only need a single early-return
+/// error (e.g. invariants enforced at construction time); callers that need
+/// to surface every failing rule to a client should call
+/// `validate_username_rules` directly.
 fn validate_username(username: &str) -> Result<(), ServerError> {
+    match validate_username_rules(username).into_iter().next() {
+        Some((field, reason)) => Err(ServerError::UserInputError { field, reason }),
+        None => Ok(()),
+    }
+}
+
+fn is_purely_numeric(username: &str) -> bool {
+    !username.is_empty() && username.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Escapes `%`, `_`, and `\` so `username` is matched as a literal string by
+/// `ILIKE` rather than as a wildcard pattern. Without this, legal usernames
+/// containing `_` (allowed by `VALID_USERNAME_CHARS`) would match any other
+/// username differing only in that position.
+fn escape_like_pattern(username: &str) -> String {
+    username.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+fn is_reserved(username: &str) -> bool {
+    RESERVED_USERNAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(username))
+}
+
+/// Runs every username rule and collects all failures, rather than stopping
+/// at the first one, so callers can surface the complete set to the client.
+pub(crate) fn validate_username_rules(username: &str) -> Vec<(String, String)> {
+    let mut errors = Vec::new();
+
     if !username.chars().all(valid_username_char) {
-        let reason = format!("invalid username characters: {:?}", username);
-        return Err(ServerError::UserInputError {
-            field: "username".into(),
-            reason,
-        });
-    } else if username.len() > MAX_USERNAME_LENGTH {
-        let reason = format!("username must be <= {} chars", MAX_USERNAME_LENGTH);
-        return Err(ServerError::UserInputError {
-            field: "username".into(),
-            reason,
-        });
+        errors.push((
+            "username".into(),
+            format!("invalid username characters: {:?}", username),
+        ));
     }
-    Ok(())
+    if username.len() < MIN_USERNAME_LENGTH {
+        errors.push((
+            "username".into(),
+            format!("username must be >= {} chars", MIN_USERNAME_LENGTH),
+        ));
+    }
+    if username.len() > MAX_USERNAME_LENGTH {
+        errors.push((
+            "username".into(),
+            format!("username must be <= {} chars", MAX_USERNAME_LENGTH),
+        ));
+    }
+    if is_purely_numeric(username) {
+        errors.push((
+            "username".into(),
+            "username cannot be purely numeric".into(),
+        ));
+    }
+    if is_reserved(username) {
+        errors.push(("username".into(), "username is reserved".into()));
+    }
+
+    errors
 }
 
 #[derive(Queryable, Identifiable, Insertable, Serialize, Deserialize, Debug)]
@@ -50,27 +104,109 @@ pub struct User {
     uid: String,
     username: String,
     pub is_guest: bool,
+    #[serde(skip_serializing)]
+    password_hash: Option<String>,
 }
 
 impl User {
-    pub fn new(uid: &str, username: &str, is_guest: bool) -> Result<User, ServerError> {
+    /// `password` is `None` for guest accounts, which can't be logged into
+    /// by uid via `/login`; they are reached through the bearer token issued
+    /// by `/guest-login` or the session cookie set by `/guest-user`.
+    pub fn new(
+        uid: &str,
+        username: &str,
+        password: Option<&str>,
+        is_guest: bool,
+    ) -> Result<User, ServerError> {
         validate_uid(uid)?;
         validate_username(username)?;
+        let password_hash = password
+            .map(|password| {
+                bcrypt::hash(password, bcrypt::DEFAULT_COST).map_err(|_| ServerError::Unauthorized {
+                    reason: "failed to hash password".into(),
+                })
+            })
+            .transpose()?;
         Ok(User {
             uid: uid.into(),
             username: username.into(),
             is_guest,
+            password_hash,
         })
     }
 
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    /// Verifies `password` against the account's stored hash. Guest
+    /// accounts have no password and always fail this check.
+    pub fn verify_password(&self, password: &str) -> bool {
+        match &self.password_hash {
+            Some(hash) => bcrypt::verify(password, hash).unwrap_or(false),
+            None => false,
+        }
+    }
+
     pub async fn find_by_uid(pool: &DbPool, uid: &str) -> Result<User, Error> {
         let conn = &mut get_conn(pool).await?;
         users_table.find(uid).first(conn).await
     }
 
+    pub async fn username_taken(pool: &DbPool, username: &str) -> Result<bool, Error> {
+        let conn = &mut get_conn(pool).await?;
+        let existing: Option<User> = users_table
+            .filter(users::username.ilike(escape_like_pattern(username)))
+            .first(conn)
+            .await
+            .optional()?;
+        Ok(existing.is_some())
+    }
+
     pub async fn insert(&self, pool: &DbPool) -> Result<(), Error> {
         let conn = &mut get_conn(pool).await?;
         self.insert_into(users_table).execute(conn).await?;
         Ok(())
     }
+
+    pub async fn set_username(&mut self, pool: &DbPool, username: &str) -> Result<(), ServerError> {
+        validate_username(username)?;
+        let conn = &mut get_conn(pool).await?;
+        diesel::update(users_table.find(self.uid.clone()))
+            .set(users::username.eq(username))
+            .execute(conn)
+            .await?;
+        self.username = username.into();
+        Ok(())
+    }
+
+    /// Converts a guest into a full account: sets the chosen username and a
+    /// real password (guests have none, and so can never log back in via
+    /// `/login`), and flips `is_guest` off, while keeping the same `uid` so
+    /// existing `games_users` rows stay intact.
+    pub async fn promote_from_guest(
+        &mut self,
+        pool: &DbPool,
+        username: &str,
+        password: &str,
+    ) -> Result<(), ServerError> {
+        validate_username(username)?;
+        let password_hash =
+            bcrypt::hash(password, bcrypt::DEFAULT_COST).map_err(|_| ServerError::Unauthorized {
+                reason: "failed to hash password".into(),
+            })?;
+        let conn = &mut get_conn(pool).await?;
+        diesel::update(users_table.find(self.uid.clone()))
+            .set((
+                users::username.eq(username),
+                users::is_guest.eq(false),
+                users::password_hash.eq(&password_hash),
+            ))
+            .execute(conn)
+            .await?;
+        self.username = username.into();
+        self.is_guest = false;
+        self.password_hash = Some(password_hash);
+        Ok(())
+    }
 }
@@ -0,0 +1,49 @@
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub reason: String,
+}
+
+impl From<(String, String)> for FieldError {
+    fn from((field, reason): (String, String)) -> Self {
+        FieldError { field, reason }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ServerError {
+    #[error("database error: {0}")]
+    Database(#[from] diesel::result::Error),
+
+    #[error("invalid {field}: {reason}")]
+    UserInputError { field: String, reason: String },
+
+    #[error("{} validation error(s)", .0.len())]
+    UserInputErrors(Vec<FieldError>),
+
+    #[error("unauthorized: {reason}")]
+    Unauthorized { reason: String },
+
+    #[error("forbidden: {reason}")]
+    Forbidden { reason: String },
+
+    #[error("CSRF token missing or mismatched")]
+    CsrfError,
+}
+
+impl ResponseError for ServerError {
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            ServerError::Database(_) => HttpResponse::InternalServerError().json(self.to_string()),
+            ServerError::UserInputError { .. } => HttpResponse::BadRequest().json(self.to_string()),
+            ServerError::UserInputErrors(errors) => HttpResponse::BadRequest().json(errors),
+            ServerError::Unauthorized { .. } => HttpResponse::Unauthorized().json(self.to_string()),
+            ServerError::Forbidden { .. } => HttpResponse::Forbidden().json(self.to_string()),
+            ServerError::CsrfError => HttpResponse::Forbidden().json(self.to_string()),
+        }
+    }
+}
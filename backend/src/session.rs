@@ -0,0 +1,16 @@
+use actix_web::cookie::{Cookie, SameSite};
+
+use crate::auth::jwt::issue_session_token;
+use crate::server_error::ServerError;
+
+pub const SESSION_COOKIE_NAME: &str = "session";
+
+pub fn session_cookie(uid: &str) -> Result<Cookie<'static>, ServerError> {
+    let token = issue_session_token(uid)?;
+    Ok(Cookie::build(SESSION_COOKIE_NAME, token)
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .path("/")
+        .finish())
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::{board::Board, direction::Direction, game_error::GameError, piece::Piece, position::Position};
+
+/// A single Universal Hive Protocol move: either a pass, or a piece placed or
+/// moved to a [`Position`] resolved relative to the rest of the board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Move {
+    Pass,
+    Move { piece: Piece, position: Position },
+}
+
+impl Move {
+    pub fn parse(s: &str, board: &Board) -> Result<Move, GameError> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("pass") {
+            return Ok(Move::Pass);
+        }
+
+        let mut parts = s.splitn(2, ' ');
+        let piece_token = parts.next().ok_or_else(|| GameError::ParsingError {
+            found: s.to_string(),
+            typ: "move".to_string(),
+        })?;
+        let piece: Piece = piece_token.parse()?;
+
+        // A bare piece code with no second token is the opening move of the
+        // game, equivalent to an explicit "." reference.
+        let position_token = parts.next().unwrap_or(".");
+        let position = Position::from_string(position_token, board)?;
+
+        Ok(Move::Move { piece, position })
+    }
+
+    pub fn to_uhp_string(&self, board: &Board) -> String {
+        match self {
+            Move::Pass => "pass".to_string(),
+            Move::Move { piece, position } => match reference_token(piece, position, board) {
+                Some(reference) => format!("{piece} {reference}"),
+                None => format!("{piece} ."),
+            },
+        }
+    }
+
+    /// Replays `moves` onto a fresh board and joins their UHP strings with
+    /// [`HISTORY_SEPARATOR`], so the result can be stored as-is and later
+    /// fed back through [`Move::parse_history`].
+    pub fn history_to_string(moves: &[Move]) -> String {
+        let mut board = Board::new();
+        moves
+            .iter()
+            .map(|mv| {
+                let uhp_string = mv.to_uhp_string(&board);
+                if let Move::Move { piece, position } = mv {
+                    board.place(piece.clone(), *position);
+                }
+                uhp_string
+            })
+            .collect::<Vec<_>>()
+            .join(HISTORY_SEPARATOR)
+    }
+
+    /// The inverse of [`Move::history_to_string`]: parses a newline-joined
+    /// UHP history back into the sequence of moves that produced it,
+    /// replaying each move onto the board as it's parsed so later moves can
+    /// resolve their relative references.
+    pub fn parse_history(history: &str) -> Result<Vec<Move>, GameError> {
+        let mut board = Board::new();
+        history
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mv = Move::parse(line, &board)?;
+                if let Move::Move { piece, position } = &mv {
+                    board.place(piece.clone(), *position);
+                }
+                Ok(mv)
+            })
+            .collect()
+    }
+}
+
+/// Separator used when persisting a game's move history, e.g. in the
+/// `games.history` column.
+pub const HISTORY_SEPARATOR: &str = "\n";
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Pass => write!(f, "pass"),
+            Move::Move { piece, position } => write!(f, "{piece} {position}"),
+        }
+    }
+}
+
+/// Finds a piece already on the board adjacent to `position` and renders it
+/// as a UHP reference token (`RefPiece±dir`), mirroring the grammar
+/// `Position::from_string` parses. `piece` is the piece being serialized and
+/// is never used as its own reference: for a piece already on the board
+/// (i.e. this is its second or later move, not a fresh placement), `board`
+/// still reflects its pre-move location, which can itself be adjacent to
+/// `position` and would otherwise be picked as a bogus self-reference.
+/// Returns `None` when the board has no *other* piece next to `position`,
+/// i.e. this is the opening placement.
+fn reference_token(piece: &Piece, position: &Position, board: &Board) -> Option<String> {
+    for direction in Direction::all() {
+        let neighbor_position = position.to(&direction);
+        if let Some(reference) = board.piece_at(&neighbor_position) {
+            if reference == piece {
+                continue;
+            }
+            let dir = neighbor_position.direction(position);
+            return Some(match dir {
+                Direction::NW => format!("\\{reference}"),
+                Direction::W => format!("-{reference}"),
+                Direction::SW => format!("/{reference}"),
+                Direction::NE => format!("{reference}/"),
+                Direction::E => format!("{reference}-"),
+                Direction::SE => format!("{reference}\\"),
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_parse_opening_move() {
+        let board = Board::new();
+        let mv = Move::parse("wS1", &board).unwrap();
+        assert_eq!(
+            mv,
+            Move::Move {
+                piece: "wS1".parse().unwrap(),
+                position: Position::new(0, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn tests_parse_pass() {
+        let board = Board::new();
+        assert_eq!(Move::parse("pass", &board).unwrap(), Move::Pass);
+    }
+
+    #[test]
+    fn tests_round_trip_opening_move() {
+        let board = Board::new();
+        let mv = Move::parse("wS1", &board).unwrap();
+        assert_eq!(mv.to_uhp_string(&board), "wS1 .");
+    }
+
+    #[test]
+    fn tests_round_trip_relative_move() {
+        let mut board = Board::new();
+        let opening: Piece = "wS1".parse().unwrap();
+        board.place(opening, Position::new(0, 0));
+
+        for token in ["bA1 -wS1", "bA1 wS1-", "bA1 /wS1", "bA1 wS1/", "bA1 \\wS1", "bA1 wS1\\"] {
+            let mv = Move::parse(token, &board).unwrap();
+            let uhp_string = mv.to_uhp_string(&board);
+            let round_tripped = Move::parse(&uhp_string, &board).unwrap();
+            assert_eq!(mv, round_tripped);
+        }
+    }
+
+    #[test]
+    fn tests_round_trip_move_of_already_placed_piece() {
+        // wS1 and bA1 are both already on the board, adjacent to each other.
+        // Moving wS1 to a square adjacent to *both* of them must reference
+        // bA1, never wS1 itself, even though wS1's own (pre-move) square is
+        // also a neighbor of the destination.
+        let mut board = Board::new();
+        let white: Piece = "wS1".parse().unwrap();
+        let black: Piece = "bA1".parse().unwrap();
+        let white_position = Position::new(0, 0);
+        let black_position = white_position.to(&Direction::NE);
+        board.place(white.clone(), white_position);
+        board.place(black.clone(), black_position);
+
+        let (destination, _) = white_position.common_adjacent_positions(&black_position);
+
+        let mv = Move::Move {
+            piece: white,
+            position: destination,
+        };
+        let uhp_string = mv.to_uhp_string(&board);
+        let reference = uhp_string.splitn(2, ' ').nth(1).unwrap();
+        assert!(
+            reference.contains("bA1") && !reference.contains("wS1"),
+            "expected a reference to bA1 (not a self-reference to wS1), got {uhp_string:?}"
+        );
+
+        let round_tripped = Move::parse(&uhp_string, &board).unwrap();
+        assert_eq!(round_tripped, mv);
+    }
+
+    #[test]
+    fn tests_history_round_trip() {
+        let moves = vec![
+            Move::parse("wS1", &Board::new()).unwrap(),
+            Move::parse("bA1 -wS1", &{
+                let mut board = Board::new();
+                board.place("wS1".parse().unwrap(), Position::new(0, 0));
+                board
+            })
+            .unwrap(),
+        ];
+
+        let history = Move::history_to_string(&moves);
+        assert_eq!(history, "wS1 .\nbA1 -wS1");
+        assert_eq!(Move::parse_history(&history).unwrap(), moves);
+    }
+
+    #[test]
+    fn tests_parse_history_ignores_trailing_blank_lines() {
+        assert_eq!(Move::parse_history("wS1\n\n").unwrap().len(), 1);
+    }
+}